@@ -0,0 +1,233 @@
+//! `research-tool bench` — run a fixed set of queries and report latency,
+//! token usage, and estimated cost.
+//!
+//! A workload file lists named queries, each with its own optional
+//! model/effort/max_tokens override. Queries within (and across) workload
+//! files run concurrently, bounded by `--parallelism`, so comparing a
+//! question across reasoning-effort levels or models doesn't mean waiting
+//! for each one serially.
+
+use std::path::PathBuf;
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+use futures_util::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+
+use crate::client::{self, ChatOpts};
+use crate::config::Config;
+use crate::{ChatMessage, Usage, DEFAULT_EFFORT, DEFAULT_MODEL, DEFAULT_SYSTEM_PROMPT};
+
+/// `research-tool bench` subcommand flags.
+#[derive(clap::Args)]
+pub struct BenchArgs {
+    /// One or more workload JSON files, each a `{"queries": [...]}` list.
+    #[arg(required = true)]
+    workloads: Vec<PathBuf>,
+
+    /// Maximum number of queries to run concurrently.
+    #[arg(long, short = 'p', default_value_t = 4)]
+    parallelism: usize,
+
+    /// Write the full report as JSON to this file (in addition to stdout).
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Deserialize)]
+struct WorkloadFile {
+    queries: Vec<WorkloadQuery>,
+}
+
+#[derive(Deserialize, Clone)]
+struct WorkloadQuery {
+    name: String,
+    query: String,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    effort: Option<String>,
+    #[serde(default)]
+    max_tokens: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct QueryResult {
+    name: String,
+    workload: String,
+    model: String,
+    effort: String,
+    latency_secs: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    usage: Option<Usage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    estimated_cost_usd: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Aggregate {
+    count: usize,
+    min_latency_secs: f64,
+    median_latency_secs: f64,
+    max_latency_secs: f64,
+    total_tokens: u64,
+    total_estimated_cost_usd: f64,
+}
+
+#[derive(Serialize)]
+struct Report {
+    results: Vec<QueryResult>,
+    aggregate: Aggregate,
+}
+
+pub async fn run(args: BenchArgs, config: Config) -> Result<()> {
+    let mut jobs = Vec::new();
+    for path in &args.workloads {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read workload file {}", path.display()))?;
+        let workload: WorkloadFile = serde_json::from_str(&text)
+            .with_context(|| format!("failed to parse workload file {}", path.display()))?;
+        let workload_name = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("workload")
+            .to_string();
+        for query in workload.queries {
+            jobs.push((workload_name.clone(), query));
+        }
+    }
+
+    let parallelism = args.parallelism.max(1);
+    let results: Vec<QueryResult> = stream::iter(jobs)
+        .map(|(workload_name, query)| run_query(&config, workload_name, query))
+        .buffer_unordered(parallelism)
+        .collect()
+        .await;
+
+    let aggregate = aggregate(&results);
+    let report = Report { results, aggregate };
+
+    let json = serde_json::to_string_pretty(&report).context("failed to serialize report")?;
+    println!("{json}");
+    if let Some(path) = &args.output {
+        std::fs::write(path, &json)
+            .with_context(|| format!("failed to write report to {}", path.display()))?;
+    }
+    Ok(())
+}
+
+async fn run_query(config: &Config, workload_name: String, query: WorkloadQuery) -> QueryResult {
+    let model = query.model.as_deref().unwrap_or(DEFAULT_MODEL);
+    let effort = query.effort.as_deref().unwrap_or(DEFAULT_EFFORT);
+    let (client_name, model) = config.resolve_model(model);
+
+    let messages = vec![
+        ChatMessage {
+            role: "system".into(),
+            content: DEFAULT_SYSTEM_PROMPT.into(),
+        },
+        ChatMessage {
+            role: "user".into(),
+            content: query.query,
+        },
+    ];
+    let opts = ChatOpts {
+        model: model.to_string(),
+        max_tokens: query.max_tokens,
+        effort: Some(effort.to_string()),
+        stream: false,
+        timeout_secs: None,
+    };
+
+    let start = Instant::now();
+    let outcome = async {
+        let client_config = config.client_config(&client_name)?;
+        let research_client = client::build_client(client_config, None)?;
+        research_client.chat(messages, opts).await
+    }
+    .await;
+    let latency_secs = start.elapsed().as_secs_f64();
+
+    match outcome {
+        Ok(result) => QueryResult {
+            name: query.name,
+            workload: workload_name,
+            model: model.to_string(),
+            effort: effort.to_string(),
+            latency_secs,
+            estimated_cost_usd: result.usage.as_ref().map(|u| {
+                let kind = config.client_config(&client_name).map(|c| c.kind.as_str()).unwrap_or("");
+                estimate_cost(kind, model, u)
+            }),
+            usage: result.usage,
+            error: None,
+        },
+        Err(e) => QueryResult {
+            name: query.name,
+            workload: workload_name,
+            model: model.to_string(),
+            effort: effort.to_string(),
+            latency_secs,
+            usage: None,
+            estimated_cost_usd: None,
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+/// Rough per-model pricing (USD per 1K tokens, prompt/completion) for
+/// well-known models, used to produce a ballpark `estimated_cost_usd`. Not
+/// exhaustive — unrecognized models fall back to a generic estimate rather
+/// than failing the whole benchmark run.
+const PRICING_PER_1K: &[(&str, f64, f64)] = &[
+    ("gpt-5.2", 0.01, 0.03),
+    ("gpt-5.2:online", 0.01, 0.03),
+    ("gpt-5.2-codex", 0.01, 0.03),
+    ("claude-opus-4-6", 0.015, 0.075),
+    ("claude-sonnet", 0.003, 0.015),
+];
+
+/// Client kinds that don't bill per token, so reporting a nonzero estimate
+/// for them would just be noise in a regression-tracking report.
+const FREE_CLIENT_KINDS: &[&str] = &["ollama"];
+
+fn estimate_cost(client_kind: &str, model: &str, usage: &Usage) -> f64 {
+    if FREE_CLIENT_KINDS.contains(&client_kind) {
+        return 0.0;
+    }
+
+    let (prompt_price, completion_price) = PRICING_PER_1K
+        .iter()
+        .find(|(name, _, _)| model.contains(name))
+        .map(|(_, p, c)| (*p, *c))
+        .unwrap_or((0.005, 0.015));
+
+    (usage.prompt_tokens as f64 / 1000.0) * prompt_price
+        + (usage.completion_tokens as f64 / 1000.0) * completion_price
+}
+
+fn aggregate(results: &[QueryResult]) -> Aggregate {
+    let mut latencies: Vec<f64> = results.iter().map(|r| r.latency_secs).collect();
+    latencies.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let median = if latencies.is_empty() {
+        0.0
+    } else {
+        latencies[latencies.len() / 2]
+    };
+
+    Aggregate {
+        count: results.len(),
+        min_latency_secs: latencies.first().copied().unwrap_or(0.0),
+        median_latency_secs: median,
+        max_latency_secs: latencies.last().copied().unwrap_or(0.0),
+        total_tokens: results
+            .iter()
+            .filter_map(|r| r.usage.as_ref())
+            .map(|u| u.total_tokens as u64)
+            .sum(),
+        total_estimated_cost_usd: results.iter().filter_map(|r| r.estimated_cost_usd).sum(),
+    }
+}
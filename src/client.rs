@@ -0,0 +1,616 @@
+//! Pluggable provider clients.
+//!
+//! research-tool was originally hard-wired to OpenRouter's
+//! `/chat/completions` endpoint. The [`Client`] trait abstracts that away so
+//! the CLI can talk to any backend; concrete providers are wired up
+//! name -> constructor via [`register_clients!`], so adding one is a few
+//! lines: a struct, a `new`, a `chat` impl, and an entry in the macro call.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use eventsource_stream::Eventsource;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+
+use crate::config::ClientConfig;
+use crate::{ChatMessage, Usage};
+
+/// Per-request options passed to [`Client::chat`].
+pub struct ChatOpts {
+    pub model: String,
+    pub max_tokens: Option<u32>,
+    pub effort: Option<String>,
+    pub stream: bool,
+    /// Total per-request timeout (the CLI's `--timeout` flag) for this call
+    /// only; the client's own `connect_timeout_secs` still applies as well.
+    pub timeout_secs: Option<u64>,
+}
+
+/// Result of a (possibly streamed) chat completion.
+pub struct ChatResult {
+    pub content: Option<String>,
+    pub reasoning: Option<String>,
+    pub usage: Option<Usage>,
+    /// Set when this client already wrote `content`/`reasoning` to
+    /// stdout/stderr as it streamed, so the caller shouldn't print them again.
+    pub streamed: bool,
+    /// URLs from the API's own `annotations`/`url_citation` field, where the
+    /// provider supports it. Does not include URLs merely mentioned in
+    /// `content` text — callers that want those too should scan `content`
+    /// themselves.
+    pub citations: Vec<String>,
+}
+
+/// A provider capable of running a chat completion.
+///
+/// Streaming implementations write content to stdout and reasoning to
+/// stderr as chunks arrive and set `ChatResult::streamed`; providers that
+/// don't support streaming should ignore `opts.stream` and return a
+/// buffered result with `streamed: false` instead of erroring, so
+/// `--stream` degrades gracefully across providers.
+#[async_trait]
+pub trait Client: Send + Sync {
+    async fn chat(&self, messages: Vec<ChatMessage>, opts: ChatOpts) -> Result<ChatResult>;
+}
+
+fn build_http_client(cfg: &ClientConfig) -> Result<reqwest::Client> {
+    let mut builder = reqwest::Client::builder();
+    if let Some(secs) = cfg.connect_timeout_secs {
+        builder = builder.connect_timeout(Duration::from_secs(secs));
+    }
+    if let Some(proxy) = &cfg.proxy {
+        builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+    }
+    builder.build().context("failed to build HTTP client")
+}
+
+fn resolve_api_key(cfg: &ClientConfig, override_key: Option<&str>) -> Result<Option<String>> {
+    if let Some(key) = override_key {
+        return Ok(Some(key.to_string()));
+    }
+    match &cfg.api_key_env {
+        Some(var) => std::env::var(var)
+            .map(Some)
+            .with_context(|| format!("{var} is not set (required by client '{}')", cfg.kind)),
+        None => Ok(None),
+    }
+}
+
+macro_rules! register_clients {
+    ($($kind:literal => $ctor:path),* $(,)?) => {
+        /// Build the concrete [`Client`] for a config entry's `kind`.
+        pub fn build_client(cfg: &ClientConfig, api_key_override: Option<&str>) -> Result<Box<dyn Client>> {
+            match cfg.kind.as_str() {
+                $($kind => Ok(Box::new($ctor(cfg, api_key_override)?)),)*
+                other => anyhow::bail!(
+                    "unknown client kind '{other}' — supported kinds: {}",
+                    [$($kind),*].join(", ")
+                ),
+            }
+        }
+    };
+}
+
+register_clients! {
+    "openrouter" => OpenRouterClient::new,
+    "openai" => OpenAiClient::new,
+    "anthropic" => AnthropicClient::new,
+    "ollama" => OllamaClient::new,
+}
+
+// ---------------------------------------------------------------------------
+// OpenAI-compatible wire shapes, shared by OpenRouter, OpenAI, and Ollama.
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize)]
+struct ChatRequest {
+    model: String,
+    messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reasoning: Option<ReasoningOpt>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+}
+
+#[derive(Serialize)]
+struct ReasoningOpt {
+    effort: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct ChatResponse {
+    choices: Option<Vec<Choice>>,
+    usage: Option<Usage>,
+    error: Option<ApiError>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Choice {
+    message: Option<MessageContent>,
+}
+
+#[derive(Deserialize, Debug)]
+struct MessageContent {
+    content: Option<String>,
+    reasoning: Option<String>,
+    #[serde(default)]
+    reasoning_content: Option<String>,
+    #[serde(default)]
+    annotations: Vec<Annotation>,
+}
+
+/// An OpenAI/OpenRouter web-search citation attached to a message.
+#[derive(Deserialize, Debug)]
+struct Annotation {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    url_citation: Option<UrlCitation>,
+}
+
+#[derive(Deserialize, Debug)]
+struct UrlCitation {
+    url: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct ApiError {
+    message: String,
+}
+
+/// One `data:` chunk of a streamed chat completion.
+#[derive(Deserialize, Debug)]
+struct ChatStreamChunk {
+    choices: Option<Vec<StreamChoice>>,
+    #[serde(default)]
+    usage: Option<Usage>,
+    #[serde(default)]
+    error: Option<ApiError>,
+}
+
+#[derive(Deserialize, Debug)]
+struct StreamChoice {
+    delta: Option<StreamDelta>,
+}
+
+#[derive(Deserialize, Debug)]
+struct StreamDelta {
+    content: Option<String>,
+    reasoning: Option<String>,
+    #[serde(default)]
+    reasoning_content: Option<String>,
+}
+
+/// Shared request/response handling for any `/chat/completions`-shaped
+/// endpoint (OpenRouter, OpenAI, Ollama's OpenAI-compatible route).
+async fn openai_compat_chat(
+    http: &reqwest::Client,
+    api_base: &str,
+    api_key: Option<&str>,
+    extra_headers: &[(&str, &str)],
+    messages: Vec<ChatMessage>,
+    opts: ChatOpts,
+) -> Result<ChatResult> {
+    let body = ChatRequest {
+        model: opts.model,
+        messages,
+        max_tokens: opts.max_tokens,
+        reasoning: opts.effort.map(|effort| ReasoningOpt { effort }),
+        stream: opts.stream,
+    };
+
+    let timeout_secs = opts.timeout_secs;
+    let url = format!("{}/chat/completions", api_base.trim_end_matches('/'));
+    let mut req = http.post(url).json(&body);
+    if let Some(key) = api_key {
+        req = req.header("Authorization", format!("Bearer {key}"));
+    }
+    for (name, value) in extra_headers {
+        req = req.header(*name, *value);
+    }
+    if let Some(secs) = timeout_secs {
+        req = req.timeout(Duration::from_secs(secs));
+    }
+
+    let resp = req
+        .send()
+        .await
+        .context("❌ Connection to provider failed — check your network and retry?")?;
+
+    eprintln!("✅ Connected — waiting for response...");
+
+    let status = resp.status();
+    if !status.is_success() {
+        let text = resp.text().await.unwrap_or_default();
+        return Err(api_status_error(status, &text));
+    }
+
+    if opts.stream {
+        stream_openai_compat(resp).await
+    } else {
+        let text = resp
+            .text()
+            .await
+            .context("❌ Connection to provider lost while waiting for response. Retry?")?;
+        parse_openai_compat(&text)
+    }
+}
+
+/// Map a non-success HTTP status to a friendlier error, falling back to the
+/// raw status/body for anything not specifically called out. Generalizes
+/// across providers since 401/402/429 mean the same thing everywhere.
+fn api_status_error(status: reqwest::StatusCode, text: &str) -> anyhow::Error {
+    match status.as_u16() {
+        401 => anyhow::anyhow!("Authentication failed (401). Check your API key for this provider."),
+        402 => anyhow::anyhow!("Insufficient credits (402). Check your provider account balance."),
+        429 => anyhow::anyhow!("Rate limited (429). Wait a moment and try again."),
+        _ => anyhow::anyhow!("API error ({status}): {text}"),
+    }
+}
+
+/// Truncate `text` to at most `max_bytes` bytes without splitting a
+/// multi-byte UTF-8 character, for embedding raw API responses in error
+/// context without risking a panic on non-ASCII text.
+fn truncate_for_context(text: &str, max_bytes: usize) -> &str {
+    let mut end = max_bytes.min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+fn parse_openai_compat(text: &str) -> Result<ChatResult> {
+    let response: ChatResponse = serde_json::from_str(text).context(format!(
+        "Failed to parse API response: {}",
+        truncate_for_context(text, 200)
+    ))?;
+
+    if let Some(error) = response.error {
+        anyhow::bail!("API error: {}", error.message);
+    }
+
+    let mut content = None;
+    let mut reasoning = None;
+    let mut citations = Vec::new();
+    if let Some(choice) = response.choices.and_then(|c| c.into_iter().next()) {
+        if let Some(msg) = choice.message {
+            reasoning = msg.reasoning.or(msg.reasoning_content);
+            content = msg.content;
+            citations = msg
+                .annotations
+                .into_iter()
+                .filter(|a| a.kind == "url_citation")
+                .filter_map(|a| a.url_citation)
+                .map(|c| c.url)
+                .collect();
+        }
+    }
+
+    Ok(ChatResult {
+        content,
+        reasoning,
+        usage: response.usage,
+        streamed: false,
+        citations,
+    })
+}
+
+async fn stream_openai_compat(resp: reqwest::Response) -> Result<ChatResult> {
+    use std::io::Write;
+
+    let mut events = resp.bytes_stream().eventsource();
+    let mut content = String::new();
+    let mut reasoning = String::new();
+    let mut usage = None;
+    let mut reasoning_started = false;
+    let mut content_started = false;
+
+    while let Some(event) = events.next().await {
+        let event = event.context("❌ Stream interrupted while waiting for response. Retry?")?;
+        if event.data == "[DONE]" {
+            break;
+        }
+
+        let chunk: ChatStreamChunk = serde_json::from_str(&event.data).context(format!(
+            "Failed to parse stream chunk: {}",
+            truncate_for_context(&event.data, 200)
+        ))?;
+
+        if let Some(error) = chunk.error {
+            anyhow::bail!("API error: {}", error.message);
+        }
+        if let Some(chunk_usage) = chunk.usage {
+            usage = Some(chunk_usage);
+        }
+
+        let Some(delta) = chunk.choices.and_then(|c| c.into_iter().next()).and_then(|c| c.delta)
+        else {
+            continue;
+        };
+
+        if let Some(r) = delta.reasoning.or(delta.reasoning_content) {
+            if !reasoning_started {
+                eprint!("\n💭 Reasoning:\n");
+                reasoning_started = true;
+            }
+            eprint!("{r}");
+            std::io::stderr().flush().ok();
+            reasoning.push_str(&r);
+        }
+
+        if let Some(c) = delta.content {
+            content_started = true;
+            print!("{c}");
+            std::io::stdout().flush().ok();
+            content.push_str(&c);
+        }
+    }
+
+    if reasoning_started {
+        eprintln!("\n---");
+    }
+    if content_started {
+        println!();
+    } else {
+        eprintln!("⚠️ No content in response");
+    }
+
+    Ok(ChatResult {
+        content: content_started.then_some(content),
+        reasoning: (!reasoning.is_empty()).then_some(reasoning),
+        usage,
+        streamed: true,
+        // Streamed deltas don't carry `annotations` the way a buffered
+        // message does; a caller that needs citations from a streamed
+        // response has to scan the printed content itself.
+        citations: Vec::new(),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// OpenRouter
+// ---------------------------------------------------------------------------
+
+pub struct OpenRouterClient {
+    http: reqwest::Client,
+    api_base: String,
+    api_key: String,
+}
+
+impl OpenRouterClient {
+    pub fn new(cfg: &ClientConfig, api_key_override: Option<&str>) -> Result<Self> {
+        let api_key = resolve_api_key(cfg, api_key_override)?
+            .context("OpenRouter requires api_key_env (or --api-key) to be set")?;
+        Ok(Self {
+            http: build_http_client(cfg)?,
+            api_base: cfg.api_base.clone(),
+            api_key,
+        })
+    }
+}
+
+#[async_trait]
+impl Client for OpenRouterClient {
+    async fn chat(&self, messages: Vec<ChatMessage>, opts: ChatOpts) -> Result<ChatResult> {
+        openai_compat_chat(
+            &self.http,
+            &self.api_base,
+            Some(&self.api_key),
+            &[
+                ("HTTP-Referer", "https://github.com/aaronn/openclaw-search-tool"),
+                ("X-Title", "OpenClaw Research Tool"),
+            ],
+            messages,
+            opts,
+        )
+        .await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Raw OpenAI
+// ---------------------------------------------------------------------------
+
+pub struct OpenAiClient {
+    http: reqwest::Client,
+    api_base: String,
+    api_key: String,
+}
+
+impl OpenAiClient {
+    pub fn new(cfg: &ClientConfig, api_key_override: Option<&str>) -> Result<Self> {
+        let api_key = resolve_api_key(cfg, api_key_override)?
+            .context("OpenAI requires api_key_env (or --api-key) to be set")?;
+        Ok(Self {
+            http: build_http_client(cfg)?,
+            api_base: cfg.api_base.clone(),
+            api_key,
+        })
+    }
+}
+
+#[async_trait]
+impl Client for OpenAiClient {
+    async fn chat(&self, messages: Vec<ChatMessage>, opts: ChatOpts) -> Result<ChatResult> {
+        openai_compat_chat(&self.http, &self.api_base, Some(&self.api_key), &[], messages, opts).await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Ollama (local, OpenAI-compatible, usually no API key)
+// ---------------------------------------------------------------------------
+
+pub struct OllamaClient {
+    http: reqwest::Client,
+    api_base: String,
+    api_key: Option<String>,
+}
+
+impl OllamaClient {
+    pub fn new(cfg: &ClientConfig, api_key_override: Option<&str>) -> Result<Self> {
+        Ok(Self {
+            http: build_http_client(cfg)?,
+            api_base: cfg.api_base.clone(),
+            api_key: resolve_api_key(cfg, api_key_override)?,
+        })
+    }
+}
+
+#[async_trait]
+impl Client for OllamaClient {
+    async fn chat(&self, messages: Vec<ChatMessage>, opts: ChatOpts) -> Result<ChatResult> {
+        openai_compat_chat(
+            &self.http,
+            &self.api_base,
+            self.api_key.as_deref(),
+            &[],
+            messages,
+            opts,
+        )
+        .await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Anthropic — distinct Messages API shape, buffered only for now.
+// ---------------------------------------------------------------------------
+
+pub struct AnthropicClient {
+    http: reqwest::Client,
+    api_base: String,
+    api_key: String,
+}
+
+impl AnthropicClient {
+    pub fn new(cfg: &ClientConfig, api_key_override: Option<&str>) -> Result<Self> {
+        let api_key = resolve_api_key(cfg, api_key_override)?
+            .context("Anthropic requires api_key_env (or --api-key) to be set")?;
+        Ok(Self {
+            http: build_http_client(cfg)?,
+            api_base: cfg.api_base.clone(),
+            api_key,
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct AnthropicRequest {
+    model: String,
+    max_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<String>,
+    messages: Vec<AnthropicMessage>,
+}
+
+#[derive(Serialize)]
+struct AnthropicMessage {
+    role: String,
+    content: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct AnthropicResponse {
+    content: Option<Vec<AnthropicContentBlock>>,
+    usage: Option<AnthropicUsage>,
+    error: Option<ApiError>,
+}
+
+#[derive(Deserialize, Debug)]
+struct AnthropicContentBlock {
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct AnthropicUsage {
+    input_tokens: u32,
+    output_tokens: u32,
+}
+
+#[async_trait]
+impl Client for AnthropicClient {
+    async fn chat(&self, messages: Vec<ChatMessage>, opts: ChatOpts) -> Result<ChatResult> {
+        // Anthropic's Messages API takes the system prompt out-of-band and
+        // only allows user/assistant turns.
+        let mut system = None;
+        let mut turns = Vec::with_capacity(messages.len());
+        for m in messages {
+            if m.role == "system" {
+                system = Some(m.content);
+            } else {
+                turns.push(AnthropicMessage {
+                    role: m.role,
+                    content: m.content,
+                });
+            }
+        }
+
+        let body = AnthropicRequest {
+            model: opts.model,
+            max_tokens: opts.max_tokens.unwrap_or(4096),
+            system,
+            messages: turns,
+        };
+
+        let url = format!("{}/messages", self.api_base.trim_end_matches('/'));
+        let mut req = self
+            .http
+            .post(url)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", "2023-06-01")
+            .json(&body);
+        if let Some(secs) = opts.timeout_secs {
+            req = req.timeout(Duration::from_secs(secs));
+        }
+
+        let resp = req
+            .send()
+            .await
+            .context("❌ Connection to Anthropic failed — check your network and retry?")?;
+
+        eprintln!("✅ Connected — waiting for response...");
+
+        let status = resp.status();
+        let text = resp
+            .text()
+            .await
+            .context("❌ Connection to Anthropic lost while waiting for response. Retry?")?;
+        if !status.is_success() {
+            return Err(api_status_error(status, &text));
+        }
+
+        let response: AnthropicResponse = serde_json::from_str(&text).context(format!(
+            "Failed to parse API response: {}",
+            truncate_for_context(&text, 200)
+        ))?;
+
+        if let Some(error) = response.error {
+            anyhow::bail!("API error: {}", error.message);
+        }
+
+        let content = response
+            .content
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|block| block.text)
+            .collect::<Vec<_>>()
+            .join("");
+
+        Ok(ChatResult {
+            content: (!content.is_empty()).then_some(content),
+            reasoning: None,
+            usage: response.usage.map(|u| Usage {
+                prompt_tokens: u.input_tokens,
+                completion_tokens: u.output_tokens,
+                total_tokens: u.input_tokens + u.output_tokens,
+            }),
+            streamed: false,
+            // Anthropic's Messages API doesn't return structured citations.
+            citations: Vec::new(),
+        })
+    }
+}
@@ -0,0 +1,171 @@
+//! Loading `~/.config/research-tool/config.yaml`.
+//!
+//! The config file lists the clients the CLI can talk to (see
+//! [`crate::client`]) and named roles (reusable system prompt + default
+//! model/effort), selectable with `--role`. When the file is absent we fall
+//! back to a single `openrouter` client and the built-in roles, so the tool
+//! behaves exactly as it always has out of the box.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// One entry under `clients:` in the config file.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ClientConfig {
+    /// Provider kind — selects which `Client` impl `register_clients!`
+    /// constructs (e.g. `openrouter`, `openai`, `anthropic`, `ollama`).
+    pub kind: String,
+    /// Base URL for the provider's API.
+    pub api_base: String,
+    /// Name of the environment variable holding the API key, if any.
+    #[serde(default)]
+    pub api_key_env: Option<String>,
+    /// Optional HTTP(S) proxy URL to route requests through.
+    #[serde(default)]
+    pub proxy: Option<String>,
+    /// Optional connect timeout, in seconds.
+    #[serde(default)]
+    pub connect_timeout_secs: Option<u64>,
+}
+
+/// One entry under `roles:` in the config file — a reusable persona.
+#[derive(Deserialize, Debug, Clone)]
+pub struct Role {
+    /// System prompt used unless `--system` is also given.
+    pub system: String,
+    /// Default `--model`, applied when `--model` is left at its own default.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Default `--effort`, applied when `--effort` is left at its own default.
+    #[serde(default)]
+    pub effort: Option<String>,
+}
+
+/// Top-level `~/.config/research-tool/config.yaml` shape.
+#[derive(Deserialize, Debug)]
+pub struct Config {
+    /// Client name used when `--model` has no recognized `provider/` prefix.
+    #[serde(default = "default_client_name")]
+    pub default_client: String,
+    /// Named clients, keyed by the name used as the `--model` prefix.
+    #[serde(default)]
+    pub clients: HashMap<String, ClientConfig>,
+    /// Named roles, keyed by the name used with `--role`.
+    #[serde(default)]
+    pub roles: HashMap<String, Role>,
+}
+
+fn default_client_name() -> String {
+    "openrouter".into()
+}
+
+fn builtin_roles() -> HashMap<String, Role> {
+    let mut roles = HashMap::new();
+    roles.insert(
+        "coder".into(),
+        Role {
+            system: "You are a senior software engineer. Give precise, idiomatic answers \
+                     with working code examples. Call out tradeoffs and edge cases; don't \
+                     pad the answer with filler."
+                .into(),
+            model: None,
+            effort: None,
+        },
+    );
+    roles.insert(
+        "fact-checker".into(),
+        Role {
+            system: "You are a rigorous fact-checker. Verify claims against current, \
+                     citable sources, flag anything you can't confirm, and include the \
+                     URLs you relied on."
+                .into(),
+            model: None,
+            effort: Some("high".into()),
+        },
+    );
+    roles
+}
+
+/// Directory holding `config.yaml` and `sessions/`, honoring `$XDG_CONFIG_HOME` via `dirs::config_dir`.
+pub fn config_dir() -> PathBuf {
+    dirs::config_dir().unwrap_or_default().join("research-tool")
+}
+
+fn config_path() -> PathBuf {
+    config_dir().join("config.yaml")
+}
+
+impl Config {
+    /// Load `config.yaml` if present, otherwise fall back to the built-in
+    /// OpenRouter-only default that matches the tool's historical behavior.
+    pub fn load() -> Result<Config> {
+        let path = config_path();
+        if !path.exists() {
+            return Ok(Config::openrouter_only());
+        }
+
+        let text = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let mut config: Config = serde_yaml::from_str(&text)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        config
+            .clients
+            .entry("openrouter".into())
+            .or_insert_with(Config::openrouter_default);
+        for (name, role) in builtin_roles() {
+            config.roles.entry(name).or_insert(role);
+        }
+        Ok(config)
+    }
+
+    fn openrouter_only() -> Config {
+        let mut clients = HashMap::new();
+        clients.insert("openrouter".into(), Config::openrouter_default());
+        Config {
+            default_client: default_client_name(),
+            clients,
+            roles: builtin_roles(),
+        }
+    }
+
+    fn openrouter_default() -> ClientConfig {
+        ClientConfig {
+            kind: "openrouter".into(),
+            api_base: "https://openrouter.ai/api/v1".into(),
+            api_key_env: Some("OPENROUTER_API_KEY".into()),
+            proxy: None,
+            connect_timeout_secs: None,
+        }
+    }
+
+    /// Split `--model` into `(client name, model)`. If `model` has a
+    /// `prefix/rest` shape and `prefix` names a configured client, that
+    /// client is used with `rest` as the model id; otherwise the whole
+    /// string is passed unchanged to `default_client`. This keeps
+    /// `openai/gpt-5.2:online` (OpenRouter's own naming) resolving to
+    /// `openrouter` unless the user has actually configured a client named
+    /// `openai`.
+    pub fn resolve_model<'a>(&self, model: &'a str) -> (String, &'a str) {
+        if let Some((prefix, rest)) = model.split_once('/') {
+            if self.clients.contains_key(prefix) {
+                return (prefix.to_string(), rest);
+            }
+        }
+        (self.default_client.clone(), model)
+    }
+
+    pub fn client_config(&self, name: &str) -> Result<&ClientConfig> {
+        self.clients
+            .get(name)
+            .with_context(|| format!("no client named '{name}' configured in config.yaml"))
+    }
+
+    pub fn role(&self, name: &str) -> Result<&Role> {
+        self.roles
+            .get(name)
+            .with_context(|| format!("no role named '{name}' — built-in roles: coder, fact-checker"))
+    }
+}
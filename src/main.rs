@@ -1,7 +1,9 @@
-//! research-tool — CLI for querying GPT-5.2:online via OpenRouter
+//! research-tool — CLI for querying GPT-5.2:online via OpenRouter (and more)
 //!
 //! A lightweight research assistant that queries OpenAI's GPT-5.2 model
-//! through OpenRouter with web search and chain-of-thought reasoning.
+//! through OpenRouter with web search and chain-of-thought reasoning. The
+//! provider isn't fixed to OpenRouter — see [`client`] and [`config`] for how
+//! `--model` resolves to a configured provider.
 //!
 //! ## Quick start
 //!
@@ -18,6 +20,23 @@
 //!   stats) goes to stderr. Pipe output to files or other tools cleanly.
 //! - **Custom system prompts**: Override the default research assistant persona
 //!   for domain-specific queries.
+//! - **Streaming**: `--stream` prints content/reasoning as each SSE chunk
+//!   arrives instead of waiting for the full response.
+//! - **Roles**: `--role <name>` loads a reusable persona (system prompt plus
+//!   optional default model/effort) from config.yaml; `coder` and
+//!   `fact-checker` ship built in.
+//! - **Sessions**: `--session <name>` (or `--continue`) persists a
+//!   conversation across invocations, replaying prior turns into each
+//!   follow-up request.
+//! - **Server mode**: `research-tool serve` starts a local OpenAI-compatible
+//!   HTTP server at `/v1/chat/completions`, for pointing other tools at
+//!   research-tool's configured provider/model/persona.
+//! - **Benchmarking**: `research-tool bench <workload.json>` runs a named set
+//!   of queries (optionally concurrently) and reports latency, token usage,
+//!   and estimated cost, for comparing models/effort levels head to head.
+//! - **Structured output**: `--output json` prints a single JSON object
+//!   (content, reasoning, model, effort, usage, elapsed_secs, citations)
+//!   instead of the text/stderr split, for scripts and agents.
 //!
 //! ## Environment variables
 //!
@@ -50,13 +69,62 @@
 //!
 //!   # Longer timeout for complex web research
 //!   research-tool --timeout 180 "What are the most popular Rust web frameworks in 2026?"
+//!
+//!   # Stream the response as it's generated
+//!   research-tool --stream "Summarize today's top Hacker News stories"
+//!
+//!   # Use a different provider configured in config.yaml
+//!   research-tool --model anthropic/claude-opus-4-6 "Explain actor-model concurrency"
+//!
+//!   # Use a built-in role instead of a long --system string
+//!   research-tool --role fact-checker "Did the 2026 Rust edition ship on schedule?"
+//!
+//!   # Start (and continue) a named research thread
+//!   research-tool --session rust-async "What's new in async Rust?"
+//!   research-tool --continue "How does that compare to Go's goroutines?"
+//!   research-tool --list-sessions
+//!   research-tool --clear-session rust-async
+//!
+//!   # Run a local OpenAI-compatible HTTP server on port 8080
+//!   research-tool serve --port 8080
+//!
+//!   # Benchmark a fixed set of queries across models/effort levels
+//!   research-tool bench workload.json --parallelism 4 --output report.json
+//!
+//!   # Machine-readable output for scripts/agents
+//!   research-tool --output json "What is the current population of Tokyo?"
+
+mod bench;
+mod client;
+mod config;
+mod output;
+mod serve;
+mod session;
 
 use std::time::Duration;
 
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use serde::{Deserialize, Serialize};
 
+use client::ChatOpts;
+use config::Config;
+use output::{JsonOutput, OutputFormat};
+use session::Session;
+
+/// Fallback model when `--model`, the active role, and the resumed session
+/// (if any) all leave it unset.
+const DEFAULT_MODEL: &str = "openai/gpt-5.2:online";
+/// Fallback effort when `--effort`, the active role, and the resumed session
+/// (if any) all leave it unset.
+const DEFAULT_EFFORT: &str = "low";
+/// Shared with `research-tool serve`, which injects the same persona when a
+/// request doesn't supply its own system message.
+pub(crate) const DEFAULT_SYSTEM_PROMPT: &str =
+    "You are a research assistant. Provide detailed, accurate answers with \
+     sources and citations where possible. Focus on factual, verifiable \
+     information. When citing web sources, include URLs.";
+
 /// Query GPT-5.2:online for research via OpenRouter.
 ///
 /// Sends your question to OpenAI's GPT-5.2 model through OpenRouter with
@@ -98,6 +166,27 @@ use serde::{Deserialize, Serialize};
         Set OPENROUTER_API_KEY in your environment or .env file.\n  \
         Get a key at https://openrouter.ai/keys"
 )]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    args: Args,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Start a local OpenAI-compatible HTTP server that forwards to the
+    /// configured provider with the tool's default model/effort/system
+    /// prompt injected.
+    Serve(serve::ServeArgs),
+
+    /// Run a workload of named queries and report latency, token usage, and
+    /// estimated cost.
+    Bench(bench::BenchArgs),
+}
+
+#[derive(clap::Args)]
 struct Args {
     /// The question or research query (multiple words joined automatically).
     /// Wrap in quotes for clarity, or just type naturally:
@@ -119,13 +208,17 @@ struct Args {
     ///   openai/gpt-5.2:online  — GPT-5.2 + web search (default)
     ///   openai/gpt-5.2         — GPT-5.2 without web search
     ///   anthropic/claude-opus-4-6 — Claude Opus (no web search)
-    #[arg(
-        long, short,
-        default_value = "openai/gpt-5.2:online",
-        env = "RESEARCH_MODEL",
-        verbatim_doc_comment
-    )]
-    model: String,
+    ///
+    /// A leading `<client>/` segment routes to that client instead of the
+    /// default, where `<client>` is a name from
+    /// ~/.config/research-tool/config.yaml (see config.rs). Without a config
+    /// file everything goes through OpenRouter, matching today's behavior.
+    ///
+    /// Left unset, a `--role`'s own default model applies, then a resumed
+    /// session's model, then the tool's built-in default
+    /// (openai/gpt-5.2:online) — in that order.
+    #[arg(long, short, env = "RESEARCH_MODEL", verbatim_doc_comment)]
+    model: Option<String>,
 
     /// Reasoning effort level — controls how much the model "thinks" before
     /// answering. Higher effort = better analysis but slower and more tokens.
@@ -133,13 +226,11 @@ struct Args {
     ///   medium — Standard analysis (~5-15s)
     ///   high   — Deep analysis with careful reasoning (~15-60s)
     ///   xhigh  — Maximum reasoning effort (~30-120s, best for complex questions)
-    #[arg(
-        long, short,
-        default_value = "low",
-        env = "RESEARCH_EFFORT",
-        verbatim_doc_comment
-    )]
-    effort: String,
+    ///
+    /// Left unset, a `--role`'s own default effort applies, then a resumed
+    /// session's effort, then the tool's built-in default (low) — in that order.
+    #[arg(long, short, env = "RESEARCH_EFFORT", verbatim_doc_comment)]
+    effort: Option<String>,
 
     /// Override the system prompt (persona/instructions for the model).
     /// Default: general research assistant that cites sources.
@@ -149,6 +240,51 @@ struct Args {
     #[arg(long, short, verbatim_doc_comment)]
     system: Option<String>,
 
+    /// Load a named persona from ~/.config/research-tool/config.yaml's
+    /// `roles` section instead of passing --system by hand. A role's system
+    /// prompt is used unless --system is also given; its optional default
+    /// model/effort apply only when --model/--effort are left at their
+    /// defaults. Built-in roles (available even without a config file):
+    ///   coder         — senior engineer persona for code-heavy questions
+    ///   fact-checker  — high-effort persona that verifies and cites sources
+    #[arg(long, verbatim_doc_comment)]
+    role: Option<String>,
+
+    /// Persist this conversation under a named session. Each run appends the
+    /// new user turn and assistant reply to
+    /// ~/.config/research-tool/sessions/<name>.json and replays the prior
+    /// turns into the request, so follow-up runs continue the thread.
+    #[arg(long)]
+    session: Option<String>,
+
+    /// Shorthand for --session <most recently used session>.
+    #[arg(long = "continue", conflicts_with = "session")]
+    continue_session: bool,
+
+    /// List all saved session names and exit.
+    #[arg(long, conflicts_with_all = ["session", "continue_session"])]
+    list_sessions: bool,
+
+    /// Delete the named session and exit.
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["session", "continue_session"])]
+    clear_session: Option<String>,
+
+    /// Stream the response token-by-token via SSE instead of waiting for the
+    /// full body. Content is written to stdout and reasoning to stderr as
+    /// each chunk arrives, so output starts appearing immediately instead of
+    /// only after the model finishes. Ignored (forced off) with
+    /// `--output json`, which needs the complete response to emit one object.
+    #[arg(long)]
+    stream: bool,
+
+    /// Output format for the final result.
+    ///   text — content on stdout, reasoning/progress/usage on stderr (default)
+    ///   json — a single JSON object on stdout (content, reasoning, model,
+    ///          effort, usage, elapsed_secs, citations), no progress chatter
+    ///          beyond the provider client's one-line connection notice
+    #[arg(long, value_enum, default_value = "text", verbatim_doc_comment)]
+    output: OutputFormat,
+
     /// Maximum number of tokens in the response. Higher values allow longer
     /// answers but cost more. Most research answers fit in 2000-4000 tokens.
     #[arg(long, default_value = "12800")]
@@ -159,65 +295,26 @@ struct Args {
     #[arg(long)]
     timeout: Option<u64>,
 
-    /// OpenRouter API key (reads from OPENROUTER_API_KEY env var by default).
-    /// Only needed as a flag to override the env var.
+    /// API key override for the resolved client (reads from that client's
+    /// `api_key_env` by default, e.g. OPENROUTER_API_KEY). Only needed as a
+    /// flag to override the env var.
     #[arg(long, env = "OPENROUTER_API_KEY", hide = true, hide_env = true)]
     api_key: Option<String>,
 }
 
-#[derive(Serialize)]
-struct ChatRequest {
-    model: String,
-    messages: Vec<ChatMessage>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    max_tokens: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    reasoning: Option<Reasoning>,
-}
-
-#[derive(Serialize)]
-struct ChatMessage {
-    role: String,
-    content: String,
-}
-
-#[derive(Serialize)]
-struct Reasoning {
-    effort: String,
-}
-
-#[derive(Deserialize, Debug)]
-struct ChatResponse {
-    choices: Option<Vec<Choice>>,
-    usage: Option<Usage>,
-    error: Option<ApiError>,
-}
-
-#[derive(Deserialize, Debug)]
-struct Choice {
-    message: Option<MessageContent>,
-}
-
-#[derive(Deserialize, Debug)]
-struct MessageContent {
-    content: Option<String>,
-    reasoning: Option<String>,
-    #[serde(default)]
-    reasoning_content: Option<String>,
+/// A single turn in a chat completion request.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct ChatMessage {
+    pub role: String,
+    pub content: String,
 }
 
-#[derive(Deserialize, Debug)]
-struct Usage {
-    prompt_tokens: u32,
-    completion_tokens: u32,
-    total_tokens: u32,
-}
-
-#[derive(Deserialize, Debug)]
-struct ApiError {
-    message: String,
-    #[allow(dead_code)]
-    code: Option<serde_json::Value>,
+/// Token accounting returned alongside a chat completion.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct Usage {
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+    pub total_tokens: u32,
 }
 
 #[tokio::main]
@@ -233,10 +330,89 @@ async fn main() -> Result<()> {
         }
     }
 
-    let args = Args::parse();
+    let cli = Cli::parse();
+
+    match cli.command {
+        Some(Command::Serve(serve_args)) => {
+            let config = Config::load().context("failed to load config.yaml")?;
+            return serve::run(serve_args, config).await;
+        }
+        Some(Command::Bench(bench_args)) => {
+            let config = Config::load().context("failed to load config.yaml")?;
+            return bench::run(bench_args, config).await;
+        }
+        None => {}
+    }
 
-    let api_key = args.api_key.unwrap_or_else(|| {
-        eprintln!("❌ No API key found.\n\nSet OPENROUTER_API_KEY in your environment:\n  export OPENROUTER_API_KEY=\"sk-or-v1-...\"\n\nGet a key at https://openrouter.ai/keys");
+    let args = cli.args;
+
+    if args.list_sessions {
+        let names = Session::list()?;
+        if names.is_empty() {
+            println!("No saved sessions.");
+        } else {
+            for name in names {
+                println!("{name}");
+            }
+        }
+        return Ok(());
+    }
+
+    if let Some(name) = &args.clear_session {
+        if Session::clear(name)? {
+            println!("Cleared session '{name}'.");
+        } else {
+            println!("No session named '{name}'.");
+        }
+        return Ok(());
+    }
+
+    let config = Config::load().context("failed to load config.yaml")?;
+
+    let role = args.role.as_deref().map(|name| config.role(name)).transpose()?;
+
+    let session_name = if args.continue_session {
+        Some(
+            Session::most_recent()?
+                .context("--continue: no saved sessions yet — start one with --session <name>")?,
+        )
+    } else {
+        args.session.clone()
+    };
+    let prior_session = session_name
+        .as_deref()
+        .map(Session::load)
+        .transpose()?
+        .flatten();
+
+    // Explicit --model/--effort always win; left unset, a role's default
+    // applies, then a resumed session's (so continuing a thread keeps using
+    // what its last run used), then the tool's own built-in default.
+    let effective_model = args
+        .model
+        .as_deref()
+        .or_else(|| role.and_then(|r| r.model.as_deref()))
+        .or(prior_session.as_ref().map(|s| s.model.as_str()))
+        .unwrap_or(DEFAULT_MODEL);
+    let effective_effort = args
+        .effort
+        .as_deref()
+        .or_else(|| role.and_then(|r| r.effort.as_deref()))
+        .or(prior_session.as_ref().map(|s| s.effort.as_str()))
+        .unwrap_or(DEFAULT_EFFORT);
+
+    let (client_name, model) = config.resolve_model(effective_model);
+    let client_config = config.client_config(&client_name)?;
+
+    // `--api-key` is also filled from OPENROUTER_API_KEY, so a value that
+    // only came from that env var is not forced onto clients that read a
+    // different `api_key_env`.
+    let api_key_override = args.api_key.as_deref().filter(|key| {
+        client_config.api_key_env.as_deref() == Some("OPENROUTER_API_KEY")
+            || std::env::var("OPENROUTER_API_KEY").ok().as_deref() != Some(*key)
+    });
+    let research_client = client::build_client(client_config, api_key_override).unwrap_or_else(|e| {
+        eprintln!("❌ {e:?}");
         std::process::exit(1);
     });
 
@@ -255,40 +431,51 @@ async fn main() -> Result<()> {
         args.query.join(" ")
     };
 
-    eprintln!("🔍 Researching with {} (effort: {})...", args.model, args.effort);
+    let json_output = args.output == OutputFormat::Json;
 
-    let mut client_builder = reqwest::Client::builder();
-    if let Some(timeout) = args.timeout {
-        client_builder = client_builder.timeout(Duration::from_secs(timeout));
+    if !json_output {
+        eprintln!(
+            "🔍 Researching with {}/{} (effort: {})...",
+            client_name, model, effective_effort
+        );
     }
-    let client = client_builder.build()?;
-
-    let mut messages = Vec::new();
-
-    let system_prompt = args.system.unwrap_or_else(|| {
-        "You are a research assistant. Provide detailed, accurate answers with \
-         sources and citations where possible. Focus on factual, verifiable \
-         information. When citing web sources, include URLs."
-            .into()
-    });
 
-    messages.push(ChatMessage {
-        role: "system".into(),
-        content: system_prompt,
-    });
+    // Resuming a session replays its whole history instead of starting a
+    // fresh system + user turn; the system prompt is whatever the session
+    // started with.
+    let mut messages = prior_session
+        .as_ref()
+        .map(|s| s.messages.clone())
+        .unwrap_or_default();
+
+    if messages.is_empty() {
+        let system_prompt = args
+            .system
+            .or_else(|| role.map(|r| r.system.clone()))
+            .unwrap_or_else(|| DEFAULT_SYSTEM_PROMPT.into());
+
+        messages.push(ChatMessage {
+            role: "system".into(),
+            content: system_prompt,
+        });
+    }
 
     messages.push(ChatMessage {
         role: "user".into(),
         content: query,
     });
 
-    let body = ChatRequest {
-        model: args.model.clone(),
-        messages,
+    let session_messages = session_name.is_some().then(|| messages.clone());
+
+    let opts = ChatOpts {
+        model: model.to_string(),
         max_tokens: Some(args.max_tokens),
-        reasoning: Some(Reasoning {
-            effort: args.effort.clone(),
-        }),
+        effort: Some(effective_effort.to_string()),
+        // --output json needs the complete response to emit a single
+        // object, so streaming (which prints chunks as they arrive) is
+        // incompatible and forced off here.
+        stream: args.stream && !json_output,
+        timeout_secs: args.timeout,
     };
 
     // Elapsed timer — prints progress ticks to stderr so agents know the process is alive
@@ -300,86 +487,62 @@ async fn main() -> Result<()> {
         loop {
             interval.tick().await;
             let elapsed = timer_start.elapsed().as_secs();
-            eprintln!("⏳ Still working... {}s elapsed", elapsed);
+            if !json_output {
+                eprintln!("⏳ Still working... {}s elapsed", elapsed);
+            }
         }
     });
 
-    let resp = client
-        .post("https://openrouter.ai/api/v1/chat/completions")
-        .header("Authorization", format!("Bearer {}", api_key))
-        .header("HTTP-Referer", "https://github.com/aaronn/openclaw-search-tool")
-        .header("X-Title", "OpenClaw Research Tool")
-        .json(&body)
-        .send()
-        .await
-        .context("❌ Connection to OpenRouter failed — check your network and retry?")?;
-
-    eprintln!("✅ Connected — waiting for response...");
-
-    let status = resp.status();
-    let text = resp.text().await.context("❌ Connection to OpenRouter lost while waiting for response. Retry?")?;
-
+    let result = research_client.chat(messages, opts).await;
     timer_handle.abort();
-
-    if !status.is_success() {
-        if status.as_u16() == 401 {
-            anyhow::bail!(
-                "Authentication failed (401). Check your OPENROUTER_API_KEY.\n\
-                 Get a key at https://openrouter.ai/keys"
-            );
-        } else if status.as_u16() == 402 {
-            anyhow::bail!(
-                "Insufficient credits (402). Add credits at https://openrouter.ai/credits"
-            );
-        } else if status.as_u16() == 429 {
-            anyhow::bail!("Rate limited (429). Wait a moment and try again.");
+    let result = result?;
+    let elapsed_secs = start.elapsed().as_secs_f64();
+
+    if json_output {
+        let output = JsonOutput::new(&result, model, effective_effort, elapsed_secs);
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&output).context("failed to serialize output")?
+        );
+    } else if !result.streamed {
+        if let Some(r) = &result.reasoning {
+            if !r.is_empty() {
+                eprintln!("\n💭 Reasoning:\n{}\n---", r);
+            }
+        }
+        if let Some(content) = &result.content {
+            println!("{}", content);
         } else {
-            anyhow::bail!("API error ({}): {}", status, text);
+            eprintln!("⚠️ No content in response");
         }
     }
 
-    let response: ChatResponse = serde_json::from_str(&text)
-        .context(format!(
-            "Failed to parse API response: {}",
-            &text[..200.min(text.len())]
-        ))?;
-
-    if let Some(error) = response.error {
-        anyhow::bail!("API error: {}", error.message);
-    }
-
-    if let Some(choices) = &response.choices {
-        if let Some(choice) = choices.first() {
-            if let Some(msg) = &choice.message {
-                // Print reasoning trace to stderr (if model returned one)
-                let reasoning = msg.reasoning.as_ref().or(msg.reasoning_content.as_ref());
-                if let Some(r) = reasoning {
-                    if !r.is_empty() {
-                        eprintln!("\n💭 Reasoning:\n{}\n---", r);
-                    }
-                }
-
-                // Print response to stdout (pipe-friendly)
-                if let Some(content) = &msg.content {
-                    println!("{}", content);
-                } else {
-                    eprintln!("⚠️ No content in response");
-                }
-            }
+    // Print usage stats to stderr
+    if !json_output {
+        let elapsed = elapsed_secs.round() as u64;
+        if let Some(usage) = &result.usage {
+            eprintln!(
+                "\n📊 Tokens: {} prompt + {} completion = {} total | ⏱ {}s",
+                usage.prompt_tokens, usage.completion_tokens, usage.total_tokens, elapsed
+            );
+        } else {
+            eprintln!("\n⏱ {}s", elapsed);
         }
-    } else {
-        eprintln!("⚠️ No choices in response");
     }
 
-    // Print usage stats to stderr
-    let elapsed = start.elapsed().as_secs();
-    if let Some(usage) = &response.usage {
-        eprintln!(
-            "\n📊 Tokens: {} prompt + {} completion = {} total | ⏱ {}s",
-            usage.prompt_tokens, usage.completion_tokens, usage.total_tokens, elapsed
-        );
-    } else {
-        eprintln!("\n⏱ {}s", elapsed);
+    if let (Some(name), Some(mut history)) = (&session_name, session_messages) {
+        if let Some(content) = &result.content {
+            history.push(ChatMessage {
+                role: "assistant".into(),
+                content: content.clone(),
+            });
+        }
+        Session {
+            model: effective_model.to_string(),
+            effort: effective_effort.to_string(),
+            messages: history,
+        }
+        .save(name)?;
     }
 
     // Force exit — reqwest's connection pool keeps tokio alive otherwise
@@ -0,0 +1,115 @@
+//! `--output json` — a single structured JSON object on stdout instead of
+//! the default text-to-stdout/metadata-to-stderr split, for scripts and
+//! agents that want the answer plus provenance as data.
+
+use serde::Serialize;
+
+use crate::client::ChatResult;
+use crate::Usage;
+
+/// Selects how a completed query is printed.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+#[value(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Human-formatted: content on stdout, reasoning/progress/usage on stderr.
+    Text,
+    /// A single JSON object on stdout; no progress chatter on stderr except
+    /// the provider client's one-line connection notice.
+    Json,
+}
+
+#[derive(Serialize)]
+pub struct JsonOutput<'a> {
+    pub content: Option<&'a str>,
+    pub reasoning: Option<&'a str>,
+    pub model: &'a str,
+    pub effort: &'a str,
+    pub usage: Option<&'a Usage>,
+    pub elapsed_secs: f64,
+    pub citations: Vec<String>,
+}
+
+impl<'a> JsonOutput<'a> {
+    pub fn new(result: &'a ChatResult, model: &'a str, effort: &'a str, elapsed_secs: f64) -> Self {
+        JsonOutput {
+            content: result.content.as_deref(),
+            reasoning: result.reasoning.as_deref().filter(|r| !r.is_empty()),
+            model,
+            effort,
+            usage: result.usage.as_ref(),
+            elapsed_secs,
+            citations: collect_citations(result),
+        }
+    }
+}
+
+/// Citations come from two sources: the API's own `annotations`
+/// (`ChatResult::citations`, populated from `message.annotations[].url_citation`
+/// where the provider returns it) and a plain scan of `content` for
+/// `http(s)://` URLs it mentions inline. The API-reported ones come first,
+/// deduplicated against each other.
+fn collect_citations(result: &ChatResult) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    let mut citations = Vec::new();
+    for url in &result.citations {
+        if seen.insert(url.clone()) {
+            citations.push(url.clone());
+        }
+    }
+    if let Some(content) = &result.content {
+        for url in extract_urls(content) {
+            if seen.insert(url.clone()) {
+                citations.push(url);
+            }
+        }
+    }
+    citations
+}
+
+/// Pull `http(s)://` URLs out of response text, in order of first
+/// appearance, deduplicated.
+fn extract_urls(content: &str) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    let mut urls = Vec::new();
+    for word in content.split_whitespace() {
+        let trimmed = word.trim_matches(|c: char| {
+            !(c.is_alphanumeric()
+                || matches!(
+                    c,
+                    '/' | '.' | '-' | '_' | '?' | '=' | '%' | '#' | ':' | '&' | '+' | '~' | ')'
+                ))
+        });
+        if !(trimmed.starts_with("http://") || trimmed.starts_with("https://")) {
+            continue;
+        }
+        let cleaned = strip_trailing_sentence_punctuation(trimmed);
+        if seen.insert(cleaned.to_string()) {
+            urls.push(cleaned.to_string());
+        }
+    }
+    urls
+}
+
+/// Strip trailing `.`/`,`/`?`/`;` (sentence punctuation, not URL syntax) and
+/// a trailing `)` only when it doesn't balance an earlier `(` in the URL
+/// itself (e.g. a Wikipedia-style URL with parens keeps its closing paren).
+fn strip_trailing_sentence_punctuation(url: &str) -> &str {
+    let bytes = url.as_bytes();
+    let mut end = url.len();
+    while end > 0 {
+        match bytes[end - 1] {
+            b'.' | b',' | b'?' | b';' => end -= 1,
+            b')' => {
+                let opens = url[..end].matches('(').count();
+                let closes = url[..end].matches(')').count();
+                if closes > opens {
+                    end -= 1;
+                } else {
+                    break;
+                }
+            }
+            _ => break,
+        }
+    }
+    &url[..end]
+}
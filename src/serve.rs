@@ -0,0 +1,213 @@
+//! `research-tool serve` — local OpenAI-compatible HTTP proxy.
+//!
+//! Exposes `POST /v1/chat/completions` accepting/returning the same shape
+//! OpenAI-compatible clients already send, forwarding each request to the
+//! configured default client with the tool's default model/effort/system
+//! prompt injected when the caller doesn't supply its own. Internally every
+//! request runs buffered against the upstream provider; a `stream: true`
+//! request gets back a single-chunk SSE response (one delta with the full
+//! text, then `[DONE]`) rather than true incremental upstream streaming —
+//! enough for clients that only care about the `stream: true` wire shape.
+//!
+//! There's no authentication on this endpoint, so it binds to loopback
+//! (`127.0.0.1`) by default; `--host` opts into wider exposure.
+
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use axum::extract::State;
+use axum::response::sse::{Event, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use futures_util::stream;
+use serde::{Deserialize, Serialize};
+
+use crate::client::{self, ChatOpts, Client};
+use crate::config::Config;
+use crate::{ChatMessage, Usage, DEFAULT_EFFORT, DEFAULT_MODEL, DEFAULT_SYSTEM_PROMPT};
+
+/// `research-tool serve` subcommand flags.
+#[derive(clap::Args)]
+pub struct ServeArgs {
+    /// Address to bind to. Defaults to loopback-only since this endpoint has
+    /// no authentication — anyone who can reach it can drive paid
+    /// completions through your configured provider and API key. Pass
+    /// 0.0.0.0 (or a LAN address) only if you've secured access some other
+    /// way (firewall, reverse proxy with auth, etc).
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: String,
+
+    /// Port to listen on.
+    #[arg(long, default_value_t = 8080)]
+    pub port: u16,
+}
+
+struct AppState {
+    client: Box<dyn Client>,
+}
+
+pub async fn run(serve_args: ServeArgs, config: Config) -> Result<()> {
+    let client_config = config.client_config(&config.default_client)?;
+    let client = client::build_client(client_config, None)?;
+    let state = Arc::new(AppState { client });
+
+    let app = Router::new()
+        .route("/v1/chat/completions", post(chat_completions))
+        .with_state(state);
+
+    let addr = format!("{}:{}", serve_args.host, serve_args.port);
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .with_context(|| format!("failed to bind {addr}"))?;
+
+    eprintln!(
+        "🔌 research-tool serve listening on http://{addr}/v1/chat/completions (provider: {})",
+        config.default_client
+    );
+    axum::serve(listener, app).await.context("server exited")?;
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct IncomingRequest {
+    #[serde(default)]
+    model: Option<String>,
+    messages: Vec<ChatMessage>,
+    #[serde(default)]
+    max_tokens: Option<u32>,
+    #[serde(default)]
+    stream: bool,
+}
+
+#[derive(Serialize)]
+struct OutgoingResponse {
+    model: String,
+    choices: Vec<OutgoingChoice>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    usage: Option<UsageJson>,
+}
+
+#[derive(Serialize)]
+struct OutgoingChoice {
+    index: u32,
+    message: OutgoingMessage,
+    finish_reason: &'static str,
+}
+
+#[derive(Serialize)]
+struct OutgoingMessage {
+    role: &'static str,
+    content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reasoning: Option<String>,
+}
+
+#[derive(Serialize)]
+struct UsageJson {
+    prompt_tokens: u32,
+    completion_tokens: u32,
+    total_tokens: u32,
+}
+
+impl From<&Usage> for UsageJson {
+    fn from(u: &Usage) -> Self {
+        UsageJson {
+            prompt_tokens: u.prompt_tokens,
+            completion_tokens: u.completion_tokens,
+            total_tokens: u.total_tokens,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct StreamDeltaChunk {
+    model: String,
+    choices: Vec<StreamDeltaChoice>,
+}
+
+#[derive(Serialize)]
+struct StreamDeltaChoice {
+    index: u32,
+    delta: OutgoingMessage,
+    finish_reason: Option<&'static str>,
+}
+
+async fn chat_completions(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<IncomingRequest>,
+) -> Response {
+    let mut messages = req.messages;
+    if !messages.iter().any(|m| m.role == "system") {
+        messages.insert(
+            0,
+            ChatMessage {
+                role: "system".into(),
+                content: DEFAULT_SYSTEM_PROMPT.into(),
+            },
+        );
+    }
+
+    let model = req.model.unwrap_or_else(|| DEFAULT_MODEL.to_string());
+    let opts = ChatOpts {
+        model: model.clone(),
+        max_tokens: req.max_tokens,
+        effort: Some(DEFAULT_EFFORT.to_string()),
+        // Streaming is handled at the HTTP layer below, not by the Client
+        // impl — its streaming path writes straight to the CLI's
+        // stdout/stderr, which would be wrong to trigger from a server.
+        stream: false,
+        timeout_secs: None,
+    };
+
+    let result = match state.client.chat(messages, opts).await {
+        Ok(result) => result,
+        Err(e) => {
+            return (
+                axum::http::StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({ "error": { "message": e.to_string() } })),
+            )
+                .into_response()
+        }
+    };
+
+    let content = result.content.unwrap_or_default();
+    let reasoning = result.reasoning.filter(|r| !r.is_empty());
+
+    if req.stream {
+        let chunk = StreamDeltaChunk {
+            model,
+            choices: vec![StreamDeltaChoice {
+                index: 0,
+                delta: OutgoingMessage {
+                    role: "assistant",
+                    content,
+                    reasoning,
+                },
+                finish_reason: Some("stop"),
+            }],
+        };
+        let events = vec![
+            Ok::<_, std::convert::Infallible>(
+                Event::default().data(serde_json::to_string(&chunk).unwrap_or_default()),
+            ),
+            Ok(Event::default().data("[DONE]")),
+        ];
+        Sse::new(stream::iter(events)).into_response()
+    } else {
+        Json(OutgoingResponse {
+            model,
+            choices: vec![OutgoingChoice {
+                index: 0,
+                message: OutgoingMessage {
+                    role: "assistant",
+                    content,
+                    reasoning,
+                },
+                finish_reason: "stop",
+            }],
+            usage: result.usage.as_ref().map(UsageJson::from),
+        })
+        .into_response()
+    }
+}
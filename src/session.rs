@@ -0,0 +1,123 @@
+//! Persistent multi-turn conversation sessions.
+//!
+//! A session is the full `Vec<ChatMessage>` history (system prompt, every
+//! user/assistant turn so far) plus the model/effort of its latest run,
+//! serialized as JSON under `~/.config/research-tool/sessions/<name>.json`.
+//! `--session <name>` replays that history into the next request and
+//! appends the new turn; `--continue` is shorthand for the most recently
+//! touched session.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::config::config_dir;
+use crate::ChatMessage;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Session {
+    pub model: String,
+    pub effort: String,
+    pub messages: Vec<ChatMessage>,
+}
+
+fn sessions_dir() -> PathBuf {
+    config_dir().join("sessions")
+}
+
+/// Session names become a filename under `sessions_dir()`, so they must not
+/// contain path separators or `.`/`..` components — otherwise a name like
+/// `../../../etc/passwd` could read or write outside that directory.
+fn validate_session_name(name: &str) -> Result<()> {
+    if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+        Ok(())
+    } else {
+        anyhow::bail!("invalid session name '{name}' — use only letters, digits, '-', and '_'");
+    }
+}
+
+fn session_path(name: &str) -> Result<PathBuf> {
+    validate_session_name(name)?;
+    Ok(sessions_dir().join(format!("{name}.json")))
+}
+
+impl Session {
+    /// Load a session by name, or `None` if it doesn't exist yet.
+    pub fn load(name: &str) -> Result<Option<Session>> {
+        let path = session_path(name)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let text = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read session '{name}'"))?;
+        let session = serde_json::from_str(&text)
+            .with_context(|| format!("failed to parse session '{name}'"))?;
+        Ok(Some(session))
+    }
+
+    /// Write this session back to disk, creating the sessions directory if needed.
+    pub fn save(&self, name: &str) -> Result<()> {
+        let dir = sessions_dir();
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("failed to create {}", dir.display()))?;
+        let text = serde_json::to_string_pretty(self).context("failed to serialize session")?;
+        std::fs::write(session_path(name)?, text)
+            .with_context(|| format!("failed to write session '{name}'"))
+    }
+
+    /// Delete a session. Returns `false` if it didn't exist.
+    pub fn clear(name: &str) -> Result<bool> {
+        let path = session_path(name)?;
+        if !path.exists() {
+            return Ok(false);
+        }
+        std::fs::remove_file(&path).with_context(|| format!("failed to remove session '{name}'"))?;
+        Ok(true)
+    }
+
+    /// Names of all saved sessions, sorted alphabetically.
+    pub fn list() -> Result<Vec<String>> {
+        let dir = sessions_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut names = Vec::new();
+        for entry in std::fs::read_dir(&dir).with_context(|| format!("failed to read {}", dir.display()))? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                    names.push(stem.to_string());
+                }
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    /// Name of the most recently modified session, for `--continue`.
+    pub fn most_recent() -> Result<Option<String>> {
+        let dir = sessions_dir();
+        if !dir.exists() {
+            return Ok(None);
+        }
+
+        let mut newest: Option<(std::time::SystemTime, String)> = None;
+        for entry in std::fs::read_dir(&dir).with_context(|| format!("failed to read {}", dir.display()))? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let modified = entry.metadata()?.modified()?;
+            if newest.as_ref().is_none_or(|(t, _)| modified > *t) {
+                newest = Some((modified, stem.to_string()));
+            }
+        }
+        Ok(newest.map(|(_, name)| name))
+    }
+}